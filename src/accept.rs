@@ -0,0 +1,31 @@
+//! Shared matching for `Accept`/`Accept-Encoding`-style headers: a comma-separated list of
+//! `token[;q=weight]` entries.
+//!
+//! Per RFC 7231 §5.3.1/§5.3.4, an entry that explicitly names a value takes precedence over a
+//! bare wildcard in the same header, even when the explicit entry's quality is lower (including
+//! `;q=0`, which excludes that value outright regardless of any wildcard alongside it).
+
+/// The quality actix-web callers should give to `value` under `header`, or `None` if nothing in
+/// the header accepts it. `wildcard` is the header's "anything goes" token (`*/*` for `Accept`,
+/// `*` for `Accept-Encoding`).
+pub(crate) fn quality(header: &str, value: &str, wildcard: &str) -> Option<f32> {
+    let mut explicit = None;
+    let mut wild = None;
+
+    for part in header.split(',') {
+        let mut segments = part.split(';');
+        let token = segments.next().unwrap_or("").trim();
+        let q: f32 = segments
+            .find_map(|p| p.trim().strip_prefix("q="))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1.0);
+
+        if token.eq_ignore_ascii_case(value) {
+            explicit = Some(q);
+        } else if token == wildcard {
+            wild = Some(q);
+        }
+    }
+
+    explicit.or(wild).filter(|q| *q > 0.0)
+}
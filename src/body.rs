@@ -1,4 +1,6 @@
-use crate::BincodePayloadError;
+use crate::{BincodeOptions, BincodePayloadError};
+#[cfg(feature = "compress")]
+use actix_web::http::header::CONTENT_ENCODING;
 use actix_web::{
     dev::Payload, http::header::CONTENT_LENGTH, web::BytesMut, HttpMessage, HttpRequest,
 };
@@ -25,6 +27,13 @@ use std::{
 pub struct BincodeBody<U> {
     pub(crate) limit: usize,
     pub(crate) length: Option<usize>,
+    pub(crate) require_length: bool,
+    pub(crate) options: BincodeOptions,
+    /// Whether the request carries a `Content-Encoding`, meaning `length` (taken from
+    /// `Content-Length`) describes the encoded size, not the size of the bytes this future
+    /// reads off `stream` once they've been decompressed.
+    #[cfg(feature = "compress")]
+    pub(crate) content_encoded: bool,
     #[cfg(feature = "compress")]
     pub(crate) stream: Option<Decompress<Payload>>,
     #[cfg(not(feature = "compress"))]
@@ -45,14 +54,15 @@ where
     ) -> Self {
         // check content-type
         let mime = req.content_type();
-        let is_good_mime = mime == "application/bincode"
-            || mime == "bincode"
-            || ctype.as_ref().map_or(false, |predicate| predicate(mime));
 
-        if !is_good_mime {
+        if !crate::config::accepts_mime(mime, ctype.as_ref()) {
             return BincodeBody {
                 limit: 262_144,
                 length: None,
+                require_length: false,
+                options: BincodeOptions::default(),
+                #[cfg(feature = "compress")]
+                content_encoded: false,
                 stream: None,
                 fut: None,
                 err: Some(BincodePayloadError::ContentType),
@@ -65,6 +75,8 @@ where
             .and_then(|l| l.to_str().ok())
             .and_then(|s| s.parse::<usize>().ok());
 
+        #[cfg(feature = "compress")]
+        let content_encoded = req.headers().contains_key(&CONTENT_ENCODING);
         #[cfg(feature = "compress")]
         let payload = Decompress::from_headers(payload.take(), req.headers());
         #[cfg(not(feature = "compress"))]
@@ -73,6 +85,10 @@ where
         BincodeBody {
             limit: 262_144,
             length: len,
+            require_length: false,
+            options: BincodeOptions::default(),
+            #[cfg(feature = "compress")]
+            content_encoded,
             stream: Some(payload),
             fut: None,
             err: None,
@@ -84,6 +100,19 @@ where
         self.limit = limit;
         self
     }
+
+    /// Change the wire options used to deserialize the payload. Default: [`BincodeOptions::default`].
+    pub fn options(mut self, options: BincodeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Reject the payload up front if it has no `Content-Length` header. By default an
+    /// unknown length is allowed and the body is read with a growable buffer.
+    pub fn require_length(mut self, require: bool) -> Self {
+        self.require_length = require;
+        self
+    }
 }
 
 impl<U> Future for BincodeBody<U>
@@ -102,26 +131,49 @@ where
         }
 
         let limit = self.limit;
-        if let Some(len) = self.length.take() {
-            if len > limit {
-                return Poll::Ready(Err(BincodePayloadError::Overflow));
+        let length = self.length.take();
+
+        // `length` comes from the request's `Content-Length`, which describes the encoded
+        // (e.g. gzip'd) entity size when `Content-Encoding` is set, while `stream` yields
+        // already-decompressed bytes in that case. Enforcing `length` against decompressed
+        // bytes would reject every legitimately compressed body, so only hold it to the
+        // decompressed byte count when there's no encoding to account for.
+        #[cfg(feature = "compress")]
+        let content_encoded = self.content_encoded;
+        #[cfg(not(feature = "compress"))]
+        let content_encoded = false;
+
+        match length {
+            Some(len) if !content_encoded && len > limit => {
+                return Poll::Ready(Err(BincodePayloadError::Overflow))
             }
+            None if self.require_length => {
+                return Poll::Ready(Err(BincodePayloadError::UnknownLength))
+            }
+            _ => {}
         }
+
         let mut stream = self.stream.take().unwrap();
+        let options = self.options;
+        let capacity = length.map_or(8192, |len| len.min(limit));
 
         self.fut = Some(
             async move {
-                let mut body = BytesMut::with_capacity(8192);
+                let mut body = BytesMut::with_capacity(capacity);
 
                 while let Some(item) = stream.next().await {
                     let chunk = item?;
-                    if (body.len() + chunk.len()) > limit {
+                    let new_len = body.len() + chunk.len();
+
+                    if new_len > limit
+                        || (!content_encoded && length.map_or(false, |len| new_len > len))
+                    {
                         return Err(BincodePayloadError::Overflow);
-                    } else {
-                        body.extend_from_slice(&chunk);
                     }
+
+                    body.extend_from_slice(&chunk);
                 }
-                Ok(bincode::deserialize(&body)?)
+                options.deserialize(&body)
             }
             .boxed_local(),
         );
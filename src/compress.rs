@@ -0,0 +1,106 @@
+//! Response-side compression negotiation, gated behind the `compress` feature.
+use crate::BincodeConfig;
+use actix_web::{http::header::ACCEPT_ENCODING, HttpRequest};
+use std::io;
+
+/// Codec usable to compress a bincode response body, mirroring the encodings actix itself
+/// already understands on the request-decoding side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BincodeContentEncoding {
+    Gzip,
+    Deflate,
+    Br,
+    Zstd,
+}
+
+impl BincodeContentEncoding {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+            Self::Br => "br",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    pub(crate) fn encode(self, body: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                use flate2::{write::GzEncoder, Compression};
+                use io::Write;
+
+                let mut enc = GzEncoder::new(Vec::new(), Compression::fast());
+                enc.write_all(body)?;
+                enc.finish()
+            }
+            Self::Deflate => {
+                use flate2::{write::DeflateEncoder, Compression};
+                use io::Write;
+
+                let mut enc = DeflateEncoder::new(Vec::new(), Compression::fast());
+                enc.write_all(body)?;
+                enc.finish()
+            }
+            Self::Br => {
+                let mut out = Vec::new();
+                let mut input = body;
+                brotli::BrotliCompress(
+                    &mut input,
+                    &mut out,
+                    &brotli::enc::BrotliEncoderParams::default(),
+                )?;
+                Ok(out)
+            }
+            Self::Zstd => zstd::stream::encode_all(body, 0),
+        }
+    }
+}
+
+/// Pick the highest-priority codec in `allowed` (server preference order) that the request's
+/// `Accept-Encoding` header accepts, or `None` (identity) when nothing matches, the header is
+/// absent, or `body_len` is below `threshold`.
+pub(crate) fn negotiate(
+    req: &HttpRequest,
+    allowed: &[BincodeContentEncoding],
+    body_len: usize,
+    threshold: usize,
+) -> Option<BincodeContentEncoding> {
+    if body_len < threshold {
+        return None;
+    }
+
+    let header = req.headers().get(&ACCEPT_ENCODING)?.to_str().ok()?;
+
+    allowed
+        .iter()
+        .copied()
+        .find(|codec| crate::accept::quality(header, codec.name(), "*").is_some())
+}
+
+/// Negotiate and apply response compression for `body` against `req`'s `Accept-Encoding`,
+/// per `config`'s codec allowlist and threshold. Returns the (possibly compressed) body and,
+/// when compression was applied, the `Content-Encoding` value the caller should set.
+///
+/// Shared between `Responder for Bincode<T>` and `HttpResponseBuilderExt::bincode2` so they
+/// can't drift on how they negotiate, encode, and log a failure.
+pub(crate) fn compress_body(
+    req: &HttpRequest,
+    config: &BincodeConfig,
+    body: Vec<u8>,
+) -> (Vec<u8>, Option<&'static str>) {
+    match negotiate(
+        req,
+        &config.compress_codecs,
+        body.len(),
+        config.compress_threshold,
+    ) {
+        Some(codec) => match codec.encode(&body) {
+            Ok(compressed) => (compressed, Some(codec.name())),
+            Err(e) => {
+                tracing::error!("Failed to compress Bincode response body: {}", e);
+                (body, None)
+            }
+        },
+        None => (body, None),
+    }
+}
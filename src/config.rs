@@ -1,25 +1,43 @@
-use crate::BincodePayloadError;
+#[cfg(feature = "compress")]
+use crate::BincodeContentEncoding;
+use crate::{BincodeOptions, BincodePayloadError};
 use actix_web::{web, HttpRequest};
 use std::sync::Arc;
 
-// Allow shared refs to default.
-const DEFAULT_CONFIG: BincodeConfig = BincodeConfig {
-    limit: 32_768, // 2^15 bytes, (~32kB)
-    err_handler: None,
-    content_type: None,
-};
-
 #[derive(Clone)]
 pub struct BincodeConfig {
     pub(crate) limit: usize,
     pub(crate) err_handler:
         Option<Arc<dyn Fn(BincodePayloadError, &HttpRequest) -> actix_web::Error + Send + Sync>>,
     pub(crate) content_type: Option<Arc<dyn Fn(&str) -> bool + Send + Sync>>,
+    pub(crate) options: BincodeOptions,
+    pub(crate) require_content_length: bool,
+    /// Minimum serialized body size, in bytes, before a response is compressed.
+    #[cfg(feature = "compress")]
+    pub(crate) compress_threshold: usize,
+    /// Codecs that may be negotiated for response compression, in priority order.
+    #[cfg(feature = "compress")]
+    pub(crate) compress_codecs: Arc<[BincodeContentEncoding]>,
 }
 
 impl Default for BincodeConfig {
     fn default() -> Self {
-        DEFAULT_CONFIG.clone()
+        BincodeConfig {
+            limit: 32_768, // 2^15 bytes, (~32kB)
+            err_handler: None,
+            content_type: None,
+            options: BincodeOptions::default(),
+            require_content_length: false,
+            #[cfg(feature = "compress")]
+            compress_threshold: 1024,
+            #[cfg(feature = "compress")]
+            compress_codecs: Arc::new([
+                BincodeContentEncoding::Br,
+                BincodeContentEncoding::Zstd,
+                BincodeContentEncoding::Gzip,
+                BincodeContentEncoding::Deflate,
+            ]),
+        }
     }
 }
 
@@ -48,11 +66,53 @@ impl BincodeConfig {
         self
     }
 
+    /// Set the bincode wire options (endianness, integer encoding, byte limit, trailing bytes)
+    /// used to serialize and deserialize payloads. Default: [`BincodeOptions::default`].
+    pub fn options(mut self, options: BincodeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Reject request bodies that have no `Content-Length` header up front, instead of
+    /// reading them with a growable buffer. Default: `false`.
+    pub fn require_content_length(mut self, require: bool) -> Self {
+        self.require_content_length = require;
+        self
+    }
+
+    /// Minimum serialized body size, in bytes, before a response is compressed. Bodies
+    /// smaller than this are sent as identity to avoid paying encoder overhead. Default:
+    /// 1024 bytes.
+    #[cfg(feature = "compress")]
+    pub fn compress_threshold(mut self, threshold: usize) -> Self {
+        self.compress_threshold = threshold;
+        self
+    }
+
+    /// Restrict which codecs may be negotiated for response compression, in priority order.
+    /// Pass an empty list to disable response compression entirely (e.g. on latency-sensitive
+    /// routes that want to skip brotli).
+    #[cfg(feature = "compress")]
+    pub fn compress_codecs(mut self, codecs: impl Into<Arc<[BincodeContentEncoding]>>) -> Self {
+        self.compress_codecs = codecs.into();
+        self
+    }
+
     /// Extract payload config from app data. Check both `T` and `Data<T>`, in that order, and fall
     /// back to the default payload config.
-    pub(crate) fn from_req(req: &HttpRequest) -> &Self {
+    pub(crate) fn from_req(req: &HttpRequest) -> Self {
         req.app_data::<Self>()
-            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
-            .unwrap_or_else(|| &DEFAULT_CONFIG)
+            .cloned()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref().clone()))
+            .unwrap_or_default()
     }
 }
+
+/// True when `mime` is an accepted bincode content type: the crate's own
+/// `application/bincode`/`bincode`, or the configured custom predicate.
+pub(crate) fn accepts_mime(
+    mime: &str,
+    ctype: Option<&Arc<dyn Fn(&str) -> bool + Send + Sync>>,
+) -> bool {
+    mime == "application/bincode" || mime == "bincode" || ctype.map_or(false, |predicate| predicate(mime))
+}
@@ -14,6 +14,12 @@ pub enum BincodePayloadError {
     Deserialize(bincode::Error),
     /// Payload error
     Payload(PayloadError),
+    /// Stream ended with an incomplete trailing frame
+    UnexpectedEof,
+    /// Bytes remained in the buffer after decoding a value while trailing bytes were rejected
+    TrailingBytes,
+    /// Payload has no `Content-Length` header while one was required
+    UnknownLength,
 }
 
 impl From<bincode::Error> for BincodePayloadError {
@@ -39,6 +45,15 @@ impl fmt::Display for BincodePayloadError {
             Self::Payload(inner) => {
                 writeln!(f, "Error that occur during reading payload: {:?}", inner)
             }
+            Self::UnexpectedEof => {
+                writeln!(f, "Bincode stream ended with an incomplete frame")
+            }
+            Self::TrailingBytes => {
+                writeln!(f, "Bytes remained after decoding a bincode value")
+            }
+            Self::UnknownLength => {
+                writeln!(f, "Content-Length header is required but was not present")
+            }
         }
     }
 }
@@ -50,6 +65,7 @@ impl ResponseError for BincodePayloadError {
     fn error_response(&self) -> BaseHttpResponse<actix_web::dev::Body> {
         match *self {
             Self::Overflow => BaseHttpResponse::new(StatusCode::PAYLOAD_TOO_LARGE),
+            Self::UnknownLength => BaseHttpResponse::new(StatusCode::LENGTH_REQUIRED),
             _ => BaseHttpResponse::new(StatusCode::BAD_REQUEST),
         }
     }
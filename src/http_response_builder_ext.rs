@@ -1,29 +1,54 @@
-use actix_web::{http::header::ContentType, HttpResponse, HttpResponseBuilder};
+#[cfg(feature = "compress")]
+use crate::compress;
+use crate::BincodeConfig;
+#[cfg(feature = "compress")]
+use actix_web::http::header::CONTENT_ENCODING;
+use actix_web::{http::header::ContentType, HttpRequest, HttpResponse, HttpResponseBuilder};
 use serde::Serialize;
 use tracing::error;
 
 /// Allow to serialize in bincode on the `HttpResponseBuilder`.
 pub trait HttpResponseBuilderExt {
-    /// Set a bincode body and generate `Response`
+    /// Set a bincode body and generate `Response`.
+    ///
+    /// `req` supplies the [`BincodeConfig`] (wire options, and, with the `compress` feature,
+    /// response compression settings) and is used to negotiate against the client's
+    /// `Accept-Encoding` header.
     ///
     /// `ResponseBuilder` can not be used after this call.
-    fn bincode<T: Serialize>(&mut self, value: T) -> HttpResponse;
+    fn bincode<T: Serialize>(&mut self, req: &HttpRequest, value: T) -> HttpResponse;
 
-    /// Set a bincode body and generate `Response`
+    /// Set a bincode body and generate `Response`.
+    ///
+    /// `req` supplies the [`BincodeConfig`] (wire options, and, with the `compress` feature,
+    /// response compression settings) and is used to negotiate against the client's
+    /// `Accept-Encoding` header.
     ///
     /// `ResponseBuilder` can not be used after this call.
-    fn bincode2<T: Serialize>(&mut self, value: &T) -> HttpResponse;
+    fn bincode2<T: Serialize>(&mut self, req: &HttpRequest, value: &T) -> HttpResponse;
 }
 
 impl HttpResponseBuilderExt for HttpResponseBuilder {
-    fn bincode<T: Serialize>(&mut self, value: T) -> HttpResponse {
-        self.bincode2(&value)
+    fn bincode<T: Serialize>(&mut self, req: &HttpRequest, value: T) -> HttpResponse {
+        self.bincode2(req, &value)
     }
 
-    fn bincode2<T: Serialize>(&mut self, value: &T) -> HttpResponse {
-        match bincode::serialize(value) {
+    fn bincode2<T: Serialize>(&mut self, req: &HttpRequest, value: &T) -> HttpResponse {
+        let config = BincodeConfig::from_req(req);
+
+        match config.options.serialize(value) {
             Ok(body) => {
                 self.insert_header(ContentType("application/bincode".parse().unwrap()));
+
+                #[cfg(feature = "compress")]
+                let body = {
+                    let (body, encoding) = compress::compress_body(req, &config, body);
+                    if let Some(name) = encoding {
+                        self.insert_header((CONTENT_ENCODING, name));
+                    }
+                    body
+                };
+
                 self.body(actix_web::dev::Body::from(body)).into()
             }
             Err(e) => {
@@ -24,6 +24,8 @@
 #[macro_use]
 extern crate serde;
 
+#[cfg(feature = "compress")]
+use actix_web::http::header::CONTENT_ENCODING;
 use actix_web::{
     dev::Payload, http::StatusCode, FromRequest, HttpRequest, HttpResponse, Responder,
 };
@@ -35,14 +37,25 @@ use std::{
 };
 
 pub use body::*;
+#[cfg(feature = "compress")]
+pub use compress::BincodeContentEncoding;
 pub use config::*;
 pub use error::*;
 pub use http_response_builder_ext::*;
+pub use negotiate::*;
+pub use options::*;
+pub use stream::*;
 
+mod accept;
 mod body;
+#[cfg(feature = "compress")]
+mod compress;
 mod config;
 mod error;
 mod http_response_builder_ext;
+mod negotiate;
+mod options;
+mod stream;
 
 #[cfg(test)]
 mod tests;
@@ -111,7 +124,9 @@ where
     T: Serialize,
 {
     fn respond_to(self, req: &HttpRequest) -> HttpResponse {
-        let body = match bincode::serialize(&self.0) {
+        let config = BincodeConfig::from_req(req);
+
+        let body = match config.options.serialize(&self.0) {
             Ok(body) => body,
             Err(e) => {
                 tracing::error!(
@@ -125,9 +140,19 @@ where
             }
         };
 
-        HttpResponse::build(StatusCode::OK)
-            .content_type("application/bincode")
-            .body(body)
+        let mut builder = HttpResponse::build(StatusCode::OK);
+        builder.content_type("application/bincode");
+
+        #[cfg(feature = "compress")]
+        let body = {
+            let (body, encoding) = compress::compress_body(req, &config, body);
+            if let Some(name) = encoding {
+                builder.insert_header((CONTENT_ENCODING, name));
+            }
+            body
+        };
+
+        builder.body(body)
     }
 }
 
@@ -146,9 +171,13 @@ where
         let limit = config.limit;
         let ctype = config.content_type.clone();
         let err_handler = config.err_handler.clone();
+        let options = config.options;
+        let require_length = config.require_content_length;
 
         BincodeBody::new(req, payload, ctype)
             .limit(limit)
+            .options(options)
+            .require_length(require_length)
             .map(move |res| match res {
                 Err(e) => {
                     tracing::debug!(
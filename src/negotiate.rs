@@ -0,0 +1,65 @@
+use crate::{accept, Bincode};
+use actix_web::{
+    http::{header, StatusCode},
+    HttpRequest, HttpResponse, Responder,
+};
+use serde::Serialize;
+
+/// `Responder` that picks bincode or JSON based on the request's `Accept` header, so one
+/// handler can serve both browser clients and internal binary clients.
+///
+/// * `application/bincode` or `bincode` is served through [`Bincode`], inheriting its wire
+///   options, response compression, and serialize-error logging.
+/// * `application/json` falls back to `serde_json`.
+/// * anything else yields `406 Not Acceptable`.
+pub struct Negotiate<T>(pub T);
+
+impl<T> Responder for Negotiate<T>
+where
+    T: Serialize,
+{
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let accept = req
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("*/*");
+
+        let bincode_q = bincode_quality(accept);
+        let json_q = accept::quality(accept, "application/json", "*/*");
+
+        // Compare the two candidates' actual quality rather than checking bincode
+        // unconditionally first, so a type matched only by a lower-weighted wildcard doesn't
+        // outrank one the client listed at a higher weight. On a tie, bincode wins.
+        if json_q.unwrap_or(0.0) > bincode_q.unwrap_or(0.0) {
+            return match serde_json::to_vec(&self.0) {
+                Ok(body) => HttpResponse::build(StatusCode::OK)
+                    .content_type("application/json")
+                    .body(body),
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to serialize to JSON. \
+                         Request path: {} \
+                         {}",
+                        req.path(),
+                        e,
+                    );
+                    HttpResponse::InternalServerError().body("Internal Server Error")
+                }
+            };
+        }
+
+        if bincode_q.is_some() {
+            return Bincode(self.0).respond_to(req);
+        }
+
+        HttpResponse::build(StatusCode::NOT_ACCEPTABLE).finish()
+    }
+}
+
+fn bincode_quality(header: &str) -> Option<f32> {
+    accept::quality(header, "application/bincode", "*/*")
+        .into_iter()
+        .chain(accept::quality(header, "bincode", "*/*"))
+        .fold(None, |best, q| Some(best.map_or(q, |b: f32| b.max(q))))
+}
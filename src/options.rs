@@ -0,0 +1,106 @@
+use crate::BincodePayloadError;
+use bincode::Options as _;
+use serde::{de::DeserializeOwned, Serialize};
+
+macro_rules! with_endian_and_int_encoding {
+    ($self:expr, $opts:ident => $body:expr) => {{
+        let base = bincode::DefaultOptions::new();
+        match ($self.big_endian, $self.varint_encoding) {
+            (false, false) => {
+                let $opts = base.with_fixint_encoding();
+                $body
+            }
+            (false, true) => {
+                let $opts = base.with_varint_encoding();
+                $body
+            }
+            (true, false) => {
+                let $opts = base.with_big_endian().with_fixint_encoding();
+                $body
+            }
+            (true, true) => {
+                let $opts = base.with_big_endian().with_varint_encoding();
+                $body
+            }
+        }
+    }};
+}
+
+/// Wire-format options applied when serializing and deserializing bincode payloads.
+///
+/// Wraps [`bincode::DefaultOptions`] to expose the knobs a peer that isn't built on this
+/// crate (or that pins a specific layout) may require: endianness, integer encoding, a byte
+/// limit, and whether trailing bytes after a value are an error.
+///
+/// The default matches the wire format `bincode::serialize`/`bincode::deserialize` have
+/// always produced: little-endian, fixed-width integers, no limit, trailing bytes allowed.
+#[derive(Debug, Clone, Copy)]
+pub struct BincodeOptions {
+    big_endian: bool,
+    varint_encoding: bool,
+    limit: Option<u64>,
+    reject_trailing_bytes: bool,
+}
+
+impl Default for BincodeOptions {
+    fn default() -> Self {
+        BincodeOptions {
+            big_endian: false,
+            varint_encoding: false,
+            limit: None,
+            reject_trailing_bytes: false,
+        }
+    }
+}
+
+impl BincodeOptions {
+    /// Encode multi-byte integers in big-endian order. Default: little-endian.
+    pub fn with_big_endian(mut self) -> Self {
+        self.big_endian = true;
+        self
+    }
+
+    /// Encode integers with bincode's variable-length encoding. Default: fixed-width, to
+    /// match the legacy `bincode::serialize`/`deserialize` wire format.
+    pub fn with_varint_encoding(mut self) -> Self {
+        self.varint_encoding = true;
+        self
+    }
+
+    /// Reject payloads larger than `limit` bytes while serializing or deserializing.
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Reject a payload if bytes remain in the buffer after decoding a single value.
+    pub fn reject_trailing_bytes(mut self) -> Self {
+        self.reject_trailing_bytes = true;
+        self
+    }
+
+    pub(crate) fn serialize<T: Serialize>(&self, value: &T) -> bincode::Result<Vec<u8>> {
+        with_endian_and_int_encoding!(self, opts => match self.limit {
+            Some(limit) => opts.with_limit(limit).serialize(value),
+            None => opts.serialize(value),
+        })
+    }
+
+    pub(crate) fn deserialize<T: DeserializeOwned>(
+        &self,
+        bytes: &[u8],
+    ) -> Result<T, BincodePayloadError> {
+        let mut cursor = std::io::Cursor::new(bytes);
+
+        let value: T = with_endian_and_int_encoding!(self, opts => match self.limit {
+            Some(limit) => opts.with_limit(limit).deserialize_from(&mut cursor),
+            None => opts.deserialize_from(&mut cursor),
+        })?;
+
+        if self.reject_trailing_bytes && cursor.position() != bytes.len() as u64 {
+            return Err(BincodePayloadError::TrailingBytes);
+        }
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{BincodeConfig, BincodeOptions, BincodePayloadError};
+#[cfg(feature = "compress")]
+use actix_web::dev::Decompress;
+use actix_web::{
+    dev::Payload, http::StatusCode, web::BytesMut, FromRequest, HttpRequest, HttpResponse,
+    Responder,
+};
+use futures_core::Stream;
+use futures_util::{
+    future::{ready, Ready},
+    StreamExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Streams a sequence of length-delimited bincode values out of a request payload.
+///
+/// Unlike [`BincodeBody`](crate::BincodeBody), which buffers the whole request body before
+/// deserializing a single value, `BincodeStream` decodes one record at a time and buffers
+/// only the bytes not yet decoded: the latest payload chunk plus any incomplete record
+/// carried over from earlier chunks.
+///
+/// Each record on the wire is framed as a little-endian `u32` length prefix followed by that
+/// many bytes of bincode for one `T`. A non-empty but incomplete trailing frame at end of
+/// stream yields [`BincodePayloadError::UnexpectedEof`].
+pub struct BincodeStream<U> {
+    limit: usize,
+    options: BincodeOptions,
+    #[cfg(feature = "compress")]
+    stream: Decompress<Payload>,
+    #[cfg(not(feature = "compress"))]
+    stream: Payload,
+    buf: BytesMut,
+    eof: bool,
+    _marker: PhantomData<U>,
+}
+
+impl<U> BincodeStream<U>
+where
+    U: DeserializeOwned,
+{
+    /// Create a `BincodeStream` reading frames from `payload`, rejecting any record whose
+    /// declared length exceeds `limit`.
+    pub fn new(
+        req: &HttpRequest,
+        payload: &mut Payload,
+        limit: usize,
+        options: BincodeOptions,
+    ) -> Self {
+        #[cfg(feature = "compress")]
+        let stream = Decompress::from_headers(payload.take(), req.headers());
+        #[cfg(not(feature = "compress"))]
+        let stream = {
+            let _ = req;
+            payload.take()
+        };
+
+        BincodeStream {
+            limit,
+            options,
+            stream,
+            buf: BytesMut::new(),
+            eof: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<U> Stream for BincodeStream<U>
+where
+    U: DeserializeOwned,
+{
+    type Item = Result<U, BincodePayloadError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.buf.len() >= 4 {
+                let n = u32::from_le_bytes(this.buf[..4].try_into().unwrap()) as usize;
+
+                if n > this.limit {
+                    this.buf.clear();
+                    this.eof = true;
+                    return Poll::Ready(Some(Err(BincodePayloadError::Overflow)));
+                }
+
+                if this.buf.len() >= 4 + n {
+                    let mut frame = this.buf.split_to(4 + n);
+                    let frame = frame.split_off(4);
+                    return Poll::Ready(Some(this.options.deserialize(&frame)));
+                }
+            }
+
+            if this.eof {
+                return if this.buf.is_empty() {
+                    Poll::Ready(None)
+                } else {
+                    this.buf.clear();
+                    Poll::Ready(Some(Err(BincodePayloadError::UnexpectedEof)))
+                };
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(chunk))) => this.buf.extend_from_slice(&chunk),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Ready(None) => this.eof = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<U> FromRequest for BincodeStream<U>
+where
+    U: DeserializeOwned + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+    type Config = BincodeConfig;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = BincodeConfig::from_req(req);
+        let limit = config.limit;
+        let ctype = config.content_type.clone();
+        let err_handler = config.err_handler.clone();
+        let options = config.options;
+
+        let mime = req.content_type();
+
+        if !crate::config::accepts_mime(mime, ctype.as_ref()) {
+            let err = BincodePayloadError::ContentType;
+            let err = match err_handler {
+                Some(err_handler) => (*err_handler)(err, req),
+                None => err.into(),
+            };
+            return ready(Err(err));
+        }
+
+        ready(Ok(BincodeStream::new(req, payload, limit, options)))
+    }
+}
+
+/// `Responder` that serializes an iterator of values into the same length-delimited framing
+/// consumed by [`BincodeStream`], letting a handler stream many records back to a client built
+/// around that extractor.
+pub struct BincodeStreamBody<I>(pub I);
+
+impl<I> Responder for BincodeStreamBody<I>
+where
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let options = BincodeConfig::from_req(req).options;
+        let mut body = BytesMut::new();
+
+        for item in self.0 {
+            match options.serialize(&item) {
+                Ok(bytes) => {
+                    body.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+                    body.extend_from_slice(&bytes);
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to serialize to Bincode. \
+                         Request path: {} \
+                         {}",
+                        req.path(),
+                        e,
+                    );
+                    return HttpResponse::InternalServerError().body("Internal Server Error");
+                }
+            }
+        }
+
+        HttpResponse::build(StatusCode::OK)
+            .content_type("application/bincode")
+            .body(body.freeze())
+    }
+}
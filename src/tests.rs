@@ -5,7 +5,12 @@ use actix_web::{
     http::header::{self, ContentType, HeaderValue},
 };
 use actix_web::{http::header::CONTENT_LENGTH, test::TestRequest};
+use futures_util::StreamExt;
 use mime::{TEXT_HTML, TEXT_PLAIN};
+#[cfg(feature = "compress")]
+use actix_web::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+#[cfg(feature = "compress")]
+use std::io::Read;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 struct MyObject {
@@ -189,3 +194,439 @@ async fn test_with_config_in_data_wrapper() {
     let err_str = s.err().unwrap().to_string();
     assert!(err_str.contains("Bincode payload size is bigger than allowed"));
 }
+
+fn framed(bytes: &[u8]) -> Vec<u8> {
+    let mut framed = (bytes.len() as u32).to_le_bytes().to_vec();
+    framed.extend_from_slice(bytes);
+    framed
+}
+
+#[actix_rt::test]
+async fn test_bincode_stream_round_trip() {
+    let a = MyObject {
+        name: "a".to_owned(),
+        number: 1,
+    };
+    let b = MyObject {
+        name: "b".to_owned(),
+        number: 2,
+    };
+
+    let mut payload = framed(&bincode::serialize(&a).unwrap());
+    payload.extend(framed(&bincode::serialize(&b).unwrap()));
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .set_payload(payload)
+        .to_http_parts();
+
+    let stream = BincodeStream::<MyObject>::from_request(&req, &mut pl)
+        .await
+        .unwrap();
+
+    let items: Vec<_> = stream.collect().await;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].as_ref().unwrap(), &a);
+    assert_eq!(items[1].as_ref().unwrap(), &b);
+}
+
+#[actix_rt::test]
+async fn test_bincode_stream_body_round_trips_through_bincode_stream() {
+    let a = MyObject {
+        name: "a".to_owned(),
+        number: 1,
+    };
+    let b = MyObject {
+        name: "b".to_owned(),
+        number: 2,
+    };
+
+    let req = TestRequest::default().to_http_request();
+    let resp = BincodeStreamBody(vec![a.clone(), b.clone()])
+        .respond_to(&req)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        header::HeaderValue::from_static("application/bincode")
+    );
+
+    let body = resp.body();
+    let payload = match body.as_ref().unwrap() {
+        Body::Bytes(b) => b.to_vec(),
+        _ => panic!("expected a bytes body"),
+    };
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .set_payload(payload)
+        .to_http_parts();
+
+    let stream = BincodeStream::<MyObject>::from_request(&req, &mut pl)
+        .await
+        .unwrap();
+
+    let items: Vec<_> = stream.collect().await;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].as_ref().unwrap(), &a);
+    assert_eq!(items[1].as_ref().unwrap(), &b);
+}
+
+#[actix_rt::test]
+async fn test_bincode_stream_truncated_frame_is_unexpected_eof() {
+    let full = framed(&bincode::serialize(&MyObject::default()).unwrap());
+    let truncated = full[..full.len() - 1].to_vec();
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .set_payload(truncated)
+        .to_http_parts();
+
+    let mut stream = BincodeStream::<MyObject>::from_request(&req, &mut pl)
+        .await
+        .unwrap();
+
+    let err = stream.next().await.unwrap().err().unwrap();
+    assert!(matches!(err, BincodePayloadError::UnexpectedEof));
+    assert!(stream.next().await.is_none());
+}
+
+#[actix_rt::test]
+async fn test_bincode_stream_oversized_frame_is_overflow_then_ends() {
+    let payload = framed(&bincode::serialize(&MyObject::default()).unwrap());
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .app_data(BincodeConfig::default().limit(4))
+        .set_payload(payload)
+        .to_http_parts();
+
+    let mut stream = BincodeStream::<MyObject>::from_request(&req, &mut pl)
+        .await
+        .unwrap();
+
+    let err = stream.next().await.unwrap().err().unwrap();
+    assert!(matches!(err, BincodePayloadError::Overflow));
+    assert!(stream.next().await.is_none());
+}
+
+#[actix_rt::test]
+async fn test_bincode_options_reject_trailing_bytes() {
+    let options = BincodeOptions::default().reject_trailing_bytes();
+    let obj = MyObject::default();
+    let mut bytes = options.serialize(&obj).unwrap();
+
+    let decoded: MyObject = options.deserialize(&bytes).unwrap();
+    assert_eq!(decoded, obj);
+
+    bytes.push(0);
+    let err = options.deserialize::<MyObject>(&bytes).err().unwrap();
+    assert!(matches!(err, BincodePayloadError::TrailingBytes));
+}
+
+#[actix_rt::test]
+async fn test_bincode_options_with_limit() {
+    let options = BincodeOptions::default().with_limit(4);
+    let err = options
+        .deserialize::<MyObject>(&bincode::serialize(&MyObject::default()).unwrap())
+        .err()
+        .unwrap();
+
+    assert!(matches!(err, BincodePayloadError::Deserialize(_)));
+}
+
+#[actix_rt::test]
+async fn test_bincode_options_with_big_endian() {
+    let options = BincodeOptions::default().with_big_endian();
+    let obj = MyObject::default();
+
+    let bytes = options.serialize(&obj).unwrap();
+    let decoded: MyObject = options.deserialize(&bytes).unwrap();
+    assert_eq!(decoded, obj);
+
+    assert_ne!(bytes, BincodeOptions::default().serialize(&obj).unwrap());
+}
+
+#[actix_rt::test]
+async fn test_bincode_options_with_varint_encoding() {
+    let options = BincodeOptions::default().with_varint_encoding();
+    let obj = MyObject::default();
+
+    let bytes = options.serialize(&obj).unwrap();
+    let decoded: MyObject = options.deserialize(&bytes).unwrap();
+    assert_eq!(decoded, obj);
+
+    assert_ne!(bytes, BincodeOptions::default().serialize(&obj).unwrap());
+}
+
+#[actix_rt::test]
+async fn test_config_options_are_honored_by_extractor_and_responder() {
+    let options = BincodeOptions::default().with_big_endian();
+    let obj = MyObject::default();
+    let big_endian_bytes = options.serialize(&obj).unwrap();
+
+    // A default-options deserialize of big-endian bytes would only coincidentally succeed,
+    // so confirm the two encodings actually disagree before relying on that to prove the
+    // config's options made it through.
+    assert_ne!(big_endian_bytes, bincode::serialize(&obj).unwrap());
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .set_payload(big_endian_bytes.clone())
+        .app_data(BincodeConfig::default().options(options))
+        .to_http_parts();
+
+    let extracted = Bincode::<MyObject>::from_request(&req, &mut pl)
+        .await
+        .unwrap();
+    assert_eq!(extracted.into_inner(), obj);
+
+    let req = TestRequest::default()
+        .app_data(BincodeConfig::default().options(options))
+        .to_http_request();
+
+    let resp = Bincode(obj).respond_to(&req).await.unwrap();
+    let body = resp.body();
+
+    if let Body::Bytes(b) = body.as_ref().unwrap() {
+        assert_eq!(b, &big_endian_bytes);
+    } else {
+        panic!("expected a bytes body");
+    }
+}
+
+#[actix_rt::test]
+async fn test_require_content_length_rejects_unknown_length() {
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .set_payload(get_test_bytes())
+        .to_http_parts();
+
+    let bc = BincodeBody::<MyObject>::new(&req, &mut pl, None)
+        .require_length(true)
+        .await;
+
+    assert!(matches!(
+        bc.err().unwrap(),
+        BincodePayloadError::UnknownLength
+    ));
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .insert_header((
+            CONTENT_LENGTH,
+            HeaderValue::from_str(&get_test_bytes().len().to_string()).unwrap(),
+        ))
+        .set_payload(get_test_bytes())
+        .to_http_parts();
+
+    let bc = BincodeBody::<MyObject>::new(&req, &mut pl, None)
+        .require_length(true)
+        .await;
+
+    assert_eq!(bc.ok().unwrap(), MyObject::default());
+}
+
+#[actix_rt::test]
+async fn test_content_length_shorter_than_body_is_overflow() {
+    let payload = get_test_bytes();
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .insert_header((CONTENT_LENGTH, HeaderValue::from_static("1")))
+        .set_payload(payload)
+        .to_http_parts();
+
+    let bc = BincodeBody::<MyObject>::new(&req, &mut pl, None).await;
+
+    assert!(bincode_eq(bc.err().unwrap(), BincodePayloadError::Overflow));
+}
+
+#[cfg(feature = "compress")]
+#[actix_rt::test]
+async fn test_compressed_body_content_length_is_encoded_size() {
+    use std::io::Write;
+
+    let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    enc.write_all(&get_test_bytes()).unwrap();
+    let compressed = enc.finish().unwrap();
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .insert_header((CONTENT_ENCODING, "gzip"))
+        .insert_header((
+            CONTENT_LENGTH,
+            HeaderValue::from_str(&compressed.len().to_string()).unwrap(),
+        ))
+        .set_payload(compressed)
+        .to_http_parts();
+
+    let bc = BincodeBody::<MyObject>::new(&req, &mut pl, None).await;
+
+    assert_eq!(bc.ok().unwrap(), MyObject::default());
+}
+
+#[cfg(feature = "compress")]
+#[actix_rt::test]
+async fn test_compressed_body_content_length_not_checked_against_limit() {
+    use std::io::Write;
+
+    let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    enc.write_all(&get_test_bytes()).unwrap();
+    let compressed = enc.finish().unwrap();
+
+    assert!(compressed.len() > get_test_bytes().len());
+
+    let (req, mut pl) = TestRequest::default()
+        .insert_header(ContentType("application/bincode".parse().unwrap()))
+        .insert_header((CONTENT_ENCODING, "gzip"))
+        .insert_header((
+            CONTENT_LENGTH,
+            HeaderValue::from_str(&compressed.len().to_string()).unwrap(),
+        ))
+        .set_payload(compressed)
+        .to_http_parts();
+
+    // The (compressed) Content-Length exceeds this limit, but the decompressed body doesn't,
+    // so the request must not be rejected before a single byte is read.
+    let bc = BincodeBody::<MyObject>::new(&req, &mut pl, None)
+        .limit(get_test_bytes().len())
+        .await;
+
+    assert_eq!(bc.ok().unwrap(), MyObject::default());
+}
+
+#[cfg(feature = "compress")]
+fn get_large_test_objs() -> Vec<MyObject> {
+    (0..200)
+        .map(|i| MyObject {
+            name: "test".to_owned(),
+            number: i,
+        })
+        .collect()
+}
+
+#[cfg(feature = "compress")]
+#[actix_rt::test]
+async fn test_compress_negotiates_codec_from_accept_encoding() {
+    let req = TestRequest::default()
+        .insert_header((ACCEPT_ENCODING, "gzip"))
+        .to_http_request();
+
+    let objs = get_large_test_objs();
+    let resp = Bincode(objs.clone()).respond_to(&req).await.unwrap();
+    assert_eq!(
+        resp.headers().get(CONTENT_ENCODING).unwrap(),
+        HeaderValue::from_static("gzip")
+    );
+
+    let body = resp.body();
+    let compressed = match body.as_ref().unwrap() {
+        Body::Bytes(b) => b,
+        _ => panic!("expected a bytes body"),
+    };
+
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(&compressed[..])
+        .read_to_end(&mut decompressed)
+        .unwrap();
+
+    assert_eq!(decompressed, bincode::serialize(&objs).unwrap());
+}
+
+#[cfg(feature = "compress")]
+#[actix_rt::test]
+async fn test_compress_below_threshold_stays_identity() {
+    let req = TestRequest::default()
+        .insert_header((ACCEPT_ENCODING, "gzip"))
+        .to_http_request();
+
+    let resp = Bincode(MyObject::default()).respond_to(&req).await.unwrap();
+    assert!(resp.headers().get(CONTENT_ENCODING).is_none());
+}
+
+#[cfg(feature = "compress")]
+#[actix_rt::test]
+async fn test_compress_codecs_allowlist_excludes_disallowed_codec() {
+    let req = TestRequest::default()
+        .insert_header((ACCEPT_ENCODING, "gzip"))
+        .app_data(BincodeConfig::default().compress_codecs([BincodeContentEncoding::Br]))
+        .to_http_request();
+
+    let resp = Bincode(get_large_test_objs())
+        .respond_to(&req)
+        .await
+        .unwrap();
+    assert!(resp.headers().get(CONTENT_ENCODING).is_none());
+}
+
+#[actix_rt::test]
+async fn test_negotiate_prefers_bincode_when_accepted() {
+    let req = TestRequest::default()
+        .insert_header((header::ACCEPT, "application/bincode"))
+        .to_http_request();
+
+    let resp = Negotiate(MyObject::default()).respond_to(&req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        HeaderValue::from_static("application/bincode")
+    );
+}
+
+#[actix_rt::test]
+async fn test_negotiate_falls_back_to_json() {
+    let req = TestRequest::default()
+        .insert_header((header::ACCEPT, "application/json"))
+        .to_http_request();
+
+    let resp = Negotiate(MyObject::default()).respond_to(&req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        HeaderValue::from_static("application/json")
+    );
+
+    let body = resp.body();
+    if let Body::Bytes(b) = body.as_ref().unwrap() {
+        let decoded: MyObject = serde_json::from_slice(b).unwrap();
+        assert_eq!(decoded, MyObject::default());
+    }
+}
+
+#[actix_rt::test]
+async fn test_negotiate_returns_not_acceptable_for_unsupported_accept() {
+    let req = TestRequest::default()
+        .insert_header((header::ACCEPT, "text/plain"))
+        .to_http_request();
+
+    let resp = Negotiate(MyObject::default()).respond_to(&req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+#[actix_rt::test]
+async fn test_negotiate_honors_q_zero_exclusion() {
+    let req = TestRequest::default()
+        .insert_header((header::ACCEPT, "application/bincode;q=0, application/json"))
+        .to_http_request();
+
+    let resp = Negotiate(MyObject::default()).respond_to(&req).await.unwrap();
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        HeaderValue::from_static("application/json")
+    );
+}
+
+#[actix_rt::test]
+async fn test_negotiate_explicit_json_outranks_trailing_wildcard() {
+    let req = TestRequest::default()
+        .insert_header((header::ACCEPT, "application/json, */*;q=0.1"))
+        .to_http_request();
+
+    let resp = Negotiate(MyObject::default()).respond_to(&req).await.unwrap();
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        HeaderValue::from_static("application/json")
+    );
+}